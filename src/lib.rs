@@ -3,7 +3,7 @@
 //! # Example
 //! ```no_run
 //! use virt2slint::Converter;
-//! let converter = Converter::new(32767,1200,800);
+//! let mut converter = Converter::new(32767,1200,800);
 //! let mut x = 0;
 //! let mut y = 0;
 //! let event = converter.convert(0x0,&mut x,&mut y).unwrap();
@@ -19,7 +19,7 @@ use virtio_input_decoder::{DecodeType, Decoder, Key, KeyType, Mouse};
 /// # Example
 /// ```no_run
 /// use virt2slint::Converter;
-/// let converter = Converter::new(32767,1200,800);
+/// let mut converter = Converter::new(32767,1200,800);
 /// let mut x = 0;
 /// let mut y = 0;
 /// let event = converter.convert(0x0,&mut x,&mut y).unwrap();
@@ -29,6 +29,42 @@ pub struct Converter {
     x_res: isize,
     y_res: isize,
     virtual_range: isize,
+    modifiers: Modifiers,
+}
+
+/// Bitflags tracking the live state of the modifier keys, so that
+/// `key2special` can decide whether a letter or symbol key should be
+/// shifted. Updated on every `KeyType::Press`/`KeyType::Release` of a
+/// Shift/Ctrl/Alt key; the CapsLock bit flips on each press of CapsLock.
+#[derive(Debug, Default, Clone, Copy)]
+struct Modifiers(u8);
+
+impl Modifiers {
+    const LSHIFT: u8 = 0b0_0001;
+    const RSHIFT: u8 = 0b0_0010;
+    const LCTRL: u8 = 0b0_0100;
+    const LALT: u8 = 0b0_1000;
+    const CAPS: u8 = 0b1_0000;
+
+    fn set(&mut self, bit: u8, on: bool) {
+        if on {
+            self.0 |= bit;
+        } else {
+            self.0 &= !bit;
+        }
+    }
+
+    fn contains(self, bit: u8) -> bool {
+        self.0 & bit != 0
+    }
+
+    fn shift(self) -> bool {
+        self.contains(Self::LSHIFT) || self.contains(Self::RSHIFT)
+    }
+
+    fn caps(self) -> bool {
+        self.contains(Self::CAPS)
+    }
 }
 
 macro_rules! press {
@@ -74,6 +110,7 @@ impl Converter {
             x_res,
             y_res,
             virtual_range,
+            modifiers: Modifiers::default(),
         }
     }
     fn scale(&self, x: isize, y: isize) -> (f32, f32) {
@@ -81,10 +118,31 @@ impl Converter {
         let y = y as f32 * self.y_res as f32 / self.virtual_range as f32;
         (x, y)
     }
+    /// Track the live state of the modifier keys as press/release events arrive.
+    ///
+    /// Shift/Ctrl/Alt are held modifiers, so their bit tracks the key's
+    /// press/release state directly. CapsLock is a toggle: it is tapped,
+    /// not held, so its bit flips on press and its release is ignored.
+    fn update_modifiers(&mut self, key: Key, key_type: KeyType) {
+        let bit = match key {
+            Key::LSHIFT => Modifiers::LSHIFT,
+            Key::RSHIFT => Modifiers::RSHIFT,
+            Key::LCTRL => Modifiers::LCTRL,
+            Key::LALT => Modifiers::LALT,
+            Key::CAPS => {
+                if matches!(key_type, KeyType::Press) {
+                    self.modifiers.set(Modifiers::CAPS, !self.modifiers.caps());
+                }
+                return;
+            }
+            _ => return,
+        };
+        self.modifiers.set(bit, matches!(key_type, KeyType::Press));
+    }
     /// Convert virtio_input_event to WindowEvent
-    pub fn convert(&self, event: u64, cx: &mut isize, cy: &mut isize) -> Option<WindowEvent> {
+    pub fn convert(&mut self, event: u64, cx: &mut isize, cy: &mut isize) -> Option<WindowEvent> {
         let decoder = u64_to_decoder(event).ok()?;
-        
+
         match decoder {
             DecodeType::Key(key, key_type) => {
                 let button = match key {
@@ -93,7 +151,8 @@ impl Converter {
                     Key::MouseRight => PointerEventButton::Right,
                     Key::MouseScrollDown | Key::MouseScrollUp => PointerEventButton::Other,
                     k => {
-                        let str = key2special(k)?;
+                        self.update_modifiers(k, key_type);
+                        let str = key2special(k, self.modifiers)?;
                         let event = match key_type {
                             KeyType::Press => WindowEvent::KeyPressed { text: str },
                             KeyType::Release => WindowEvent::KeyReleased { text: str },
@@ -145,7 +204,27 @@ fn u64_to_decoder(event: u64) -> Result<DecodeType, ()> {
     Decoder::decode(dtype, code as usize, val as isize)
 }
 
-fn key2special(key: Key) -> Option<SharedString> {
+/// Letter case follows `shift XOR caps`, mirroring how a real keyboard
+/// driver combines Shift and CapsLock for `ReceivedCharacter`-style text.
+fn cased(lower: char, modifiers: Modifiers) -> char {
+    if modifiers.shift() ^ modifiers.caps() {
+        lower.to_ascii_uppercase()
+    } else {
+        lower
+    }
+}
+
+/// Number/punctuation row follows the standard US shift table; CapsLock
+/// does not affect these keys.
+fn shifted(plain: char, shifted: char, modifiers: Modifiers) -> char {
+    if modifiers.shift() {
+        shifted
+    } else {
+        plain
+    }
+}
+
+fn key2special(key: Key, modifiers: Modifiers) -> Option<SharedString> {
     let key = match key {
         Key::ESC => slint::platform::Key::Escape.into(),
         Key::BackSpace => slint::platform::Key::Backspace.into(),
@@ -156,51 +235,51 @@ fn key2special(key: Key) -> Option<SharedString> {
         Key::RSHIFT => slint::platform::Key::ShiftR.into(),
         Key::LALT => slint::platform::Key::Alt.into(),
         Key::CAPS => slint::platform::Key::CapsLock.into(),
-        Key::A => 'a'.into(),
-        Key::B => 'b'.into(),
-        Key::C => 'c'.into(),
-        Key::D => 'd'.into(),
-        Key::E => 'e'.into(),
-        Key::F => 'f'.into(),
-        Key::G => 'g'.into(),
-        Key::H => 'h'.into(),
-        Key::I => 'i'.into(),
-        Key::J => 'j'.into(),
-        Key::K => 'k'.into(),
-        Key::L => 'l'.into(),
-        Key::M => 'm'.into(),
-        Key::N => 'n'.into(),
-        Key::O => 'o'.into(),
-        Key::P => 'p'.into(),
-        Key::Q => 'q'.into(),
-        Key::R => 'r'.into(),
-        Key::S => 's'.into(),
-        Key::T => 't'.into(),
-        Key::U => 'u'.into(),
-        Key::V => 'v'.into(),
-        Key::W => 'w'.into(),
-        Key::X => 'x'.into(),
-        Key::Y => 'y'.into(),
-        Key::Z => 'z'.into(),
-        Key::Zero => '0'.into(),
-        Key::One => '1'.into(),
-        Key::Two => '2'.into(),
-        Key::Three => '3'.into(),
-        Key::Four => '4'.into(),
-        Key::Five => '5'.into(),
-        Key::Six => '6'.into(),
-        Key::Seven => '7'.into(),
-        Key::Eight => '8'.into(),
-        Key::Nine => '9'.into(),
+        Key::A => cased('a', modifiers).into(),
+        Key::B => cased('b', modifiers).into(),
+        Key::C => cased('c', modifiers).into(),
+        Key::D => cased('d', modifiers).into(),
+        Key::E => cased('e', modifiers).into(),
+        Key::F => cased('f', modifiers).into(),
+        Key::G => cased('g', modifiers).into(),
+        Key::H => cased('h', modifiers).into(),
+        Key::I => cased('i', modifiers).into(),
+        Key::J => cased('j', modifiers).into(),
+        Key::K => cased('k', modifiers).into(),
+        Key::L => cased('l', modifiers).into(),
+        Key::M => cased('m', modifiers).into(),
+        Key::N => cased('n', modifiers).into(),
+        Key::O => cased('o', modifiers).into(),
+        Key::P => cased('p', modifiers).into(),
+        Key::Q => cased('q', modifiers).into(),
+        Key::R => cased('r', modifiers).into(),
+        Key::S => cased('s', modifiers).into(),
+        Key::T => cased('t', modifiers).into(),
+        Key::U => cased('u', modifiers).into(),
+        Key::V => cased('v', modifiers).into(),
+        Key::W => cased('w', modifiers).into(),
+        Key::X => cased('x', modifiers).into(),
+        Key::Y => cased('y', modifiers).into(),
+        Key::Z => cased('z', modifiers).into(),
+        Key::Zero => shifted('0', ')', modifiers).into(),
+        Key::One => shifted('1', '!', modifiers).into(),
+        Key::Two => shifted('2', '@', modifiers).into(),
+        Key::Three => shifted('3', '#', modifiers).into(),
+        Key::Four => shifted('4', '$', modifiers).into(),
+        Key::Five => shifted('5', '%', modifiers).into(),
+        Key::Six => shifted('6', '^', modifiers).into(),
+        Key::Seven => shifted('7', '&', modifiers).into(),
+        Key::Eight => shifted('8', '*', modifiers).into(),
+        Key::Nine => shifted('9', '(', modifiers).into(),
         Key::Space => ' '.into(),
-        Key::Minus => '-'.into(),
-        Key::Equal => '='.into(),
-        Key::BackSlash => '\\'.into(),
-        Key::Colon => ';'.into(),
-        Key::Comma => ','.into(),
-        Key::Dot => '.'.into(),
-        Key::SineglePoint => '\''.into(),
-        Key::Slash => '/'.into(),
+        Key::Minus => shifted('-', '_', modifiers).into(),
+        Key::Equal => shifted('=', '+', modifiers).into(),
+        Key::BackSlash => shifted('\\', '|', modifiers).into(),
+        Key::Colon => shifted(';', ':', modifiers).into(),
+        Key::Comma => shifted(',', '<', modifiers).into(),
+        Key::Dot => shifted('.', '>', modifiers).into(),
+        Key::SineglePoint => shifted('\'', '"', modifiers).into(),
+        Key::Slash => shifted('/', '?', modifiers).into(),
         _ => return None,
     };
     Some(key)